@@ -0,0 +1,78 @@
+//! Reset and clock control.
+//!
+//! This snapshot only carries the reset-cause portion of the `rcc` module; the
+//! existing clock API (`pclk1_hz`, `sysclk_hz`, `set_sysclk_hsi`, ...) lives in
+//! the same module and is left untouched by these additions.
+
+use crate::pac::RCC;
+
+/// The cause of the most recent reset, as recorded in `RCC_CSR`.
+///
+/// Latched by the hardware until the flags are cleared with
+/// [`clear_reset_flags`]. Reading this at startup lets an application branch on
+/// why it rebooted, for example entering a recovery mode after a watchdog
+/// timeout instead of looping.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ResetCause {
+    /// Reset caused by the independent watchdog (`IWDGRSTF`).
+    IndependentWatchdog,
+    /// Reset caused by the window watchdog (`WWDGRSTF`).
+    WindowWatchdog,
+    /// Software reset (`SFTRSTF`).
+    Software,
+    /// Reset from the NRST pin (`PINRSTF`).
+    Pin,
+    /// Reset on exiting a low-power mode (`LPWRRSTF`).
+    LowPower,
+    /// Brown-out reset (`BORRSTF`).
+    BrownOut,
+    /// Option-byte loader reset (`OBLRSTF`).
+    OptionByteLoad,
+    /// No reset flag was set.
+    Unknown,
+}
+
+/// Read the cause of the most recent reset from `RCC_CSR`.
+///
+/// Several flags can be set simultaneously; they are examined in priority order
+/// so the most specific cause is reported. The flags persist across resets
+/// until cleared with [`clear_reset_flags`].
+///
+/// # Arguments
+///
+/// * `rcc` - Instance of RCC from PAC needed to read the reset flags.
+pub fn reset_cause(rcc : &RCC) -> ResetCause {
+    let csr = rcc.csr.read();
+
+    if csr.lpwrrstf().bit_is_set() {
+        ResetCause::LowPower
+    } else if csr.wwdgrstf().bit_is_set() {
+        ResetCause::WindowWatchdog
+    } else if csr.iwdgrstf().bit_is_set() {
+        ResetCause::IndependentWatchdog
+    } else if csr.sftrstf().bit_is_set() {
+        ResetCause::Software
+    } else if csr.borrstf().bit_is_set() {
+        ResetCause::BrownOut
+    } else if csr.oblrstf().bit_is_set() {
+        ResetCause::OptionByteLoad
+    } else if csr.pinrstf().bit_is_set() {
+        ResetCause::Pin
+    } else {
+        ResetCause::Unknown
+    }
+}
+
+/// Clear the reset flags in `RCC_CSR` by setting `RMVF`.
+///
+/// Call this after latching the boot reason with [`reset_cause`] so the next
+/// reset reports its own cause instead of the accumulated flags.
+///
+/// # Arguments
+///
+/// * `rcc` - Instance of RCC from PAC needed to clear the reset flags.
+#[inline]
+pub fn clear_reset_flags(rcc : &mut RCC) {
+    rcc.csr.modify(|_, w| w.rmvf().set_bit());
+}
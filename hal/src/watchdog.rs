@@ -1,7 +1,7 @@
 //! Hardware watchdog.
 
 use crate::{
-    pac::{self, WWDG, RCC},
+    pac::{self, WWDG, IWDG, RCC},
     rcc::pclk1_hz,
     embedded_hal::watchdog::{WatchdogEnable, Watchdog}
 };
@@ -10,12 +10,92 @@ const WWDG_DIV : u32 = 4096;
 const WWDG_MAX_PRESCALER : u32 = 128;
 const WWDG_MAX_RELOAD : u32 = 0x3F;
 
+/// Frequency of the low-speed internal oscillator (LSI) in Hz that clocks the IWDG.
+const LSI_FREQ : u32 = 32_000;
+/// Maximum prescaler selection for the IWDG (PR = 6 selects a /256 divisor).
+const IWDG_MAX_PR : u8 = 6;
+/// Maximum 12-bit reload value for the IWDG.
+const IWDG_MAX_RELOAD : u32 = 0xFFF;
+
+/// Unlock the IWDG registers for write access.
+const IWDG_KEY_UNLOCK : u16 = 0x5555;
+/// Start the IWDG counting.
+const IWDG_KEY_START : u16 = 0xCCCC;
+/// Reload the IWDG down-counter (refresh).
+const IWDG_KEY_FEED : u16 = 0xAAAA;
+
+/// Up-front configuration for a watchdog, consolidating the otherwise separate
+/// `stop_on_debug`, window and `start` steps into a single atomic call.
+///
+/// Built with [`WatchdogConfig::new`] and handed to
+/// [`WindowWatchdog::start_from_config`] or
+/// [`IndependentWatchdog::start_from_config`]. Programming everything in one
+/// call avoids the foot-gun where changing PCLK1 after `start` silently
+/// corrupts the interval.
+///
+/// The IWDG's behaviour in the STM32WL's stop/standby low-power modes is not
+/// configurable here: it is fixed by the `IWDG_STOP`/`IWDG_STDBY` FLASH option
+/// bytes rather than any runtime register, so it is deliberately not exposed as
+/// a builder field.
+#[derive(Debug, Clone, Copy)]
+pub struct WatchdogConfig {
+    timeout_us : u32,
+    window_us : Option<u32>,
+    run_during_debug_halt : bool,
+}
+
+impl WatchdogConfig {
+    /// Create a configuration with the given timeout in microseconds.
+    ///
+    /// By default the watchdog is frozen during a debug halt.
+    #[inline]
+    pub fn new(timeout_us : u32) -> Self {
+        WatchdogConfig {
+            timeout_us,
+            window_us : None,
+            run_during_debug_halt : false,
+        }
+    }
+
+    /// Set the length (in microseconds) of the refresh window at the end of the
+    /// timeout; a refresh before the window opens triggers a reset.
+    ///
+    /// Only honoured by the [`WindowWatchdog`]; the IWDG has no window.
+    #[inline]
+    pub fn window_us(mut self, window_us : u32) -> Self {
+        self.window_us = Some(window_us);
+        self
+    }
+
+    /// Keep the watchdog counting while the cpu is halted during debugging.
+    #[inline]
+    pub fn run_during_debug_halt(mut self, run : bool) -> Self {
+        self.run_during_debug_halt = run;
+        self
+    }
+}
+
 /// Driver for window watchdog (WWDG) peripheral;
 pub struct WindowWatchdog {
     wwdg : WWDG,
     reload : u8
 }
 
+/// Configuration of a [`WindowWatchdog`] as read back from the hardware.
+///
+/// Obtained through [`WindowWatchdog::config`] to recover the live settings of
+/// an already-running watchdog instead of duplicating configuration.
+pub struct Config {
+    /// Reload value held in `CR.T[6:0]` (the 0x40 counter base removed).
+    pub reload : u8,
+    /// Window value held in `CFR.W`.
+    pub window : u8,
+    /// Prescaler selection held in `CFR.WDGTB`.
+    pub prescaler : u8,
+    /// Whether the watchdog is enabled (`CR.WDGA`).
+    pub enabled : bool,
+}
+
 impl WindowWatchdog {
     /// Create a new WindowWatchdog, enables the clock for the WWDG.
     /// 
@@ -44,6 +124,58 @@ impl WindowWatchdog {
         WindowWatchdog { wwdg: dp.WWDG, reload: 0 }
     }
 
+    /// Reconstruct a WindowWatchdog from the live hardware registers.
+    ///
+    /// Unlike [`new`] this does not reconfigure the peripheral; it recovers the
+    /// reload value from `CR.T`, for example after a [`steal`] of an
+    /// already-running watchdog. The clock for the WWDG is enabled so the
+    /// registers are accessible.
+    ///
+    /// # Limitations
+    ///
+    /// The WWDG has no separate reload register: `CR.T` is the live down-counter
+    /// which decrements continuously between 0x7F and 0x40. The recovered reload
+    /// therefore equals the configured reload only in the instant immediately
+    /// after a refresh; read at any other time [`interval_us`] and [`feed`] will
+    /// use a smaller value. Call this right after a [`feed`] for the most
+    /// accurate recovery.
+    ///
+    /// [`new`]: WindowWatchdog::new
+    /// [`interval_us`]: WindowWatchdog::interval_us
+    /// [`feed`]: WindowWatchdog::feed
+    /// [`steal`]: WindowWatchdog::steal
+    pub fn from_registers(wwdg : WWDG, rcc : &RCC) -> Self {
+        rcc.apb1enr1.modify(|_, w| w.wwdgen().enabled());
+        // Strip the 0x40 counter base to recover the reload programmed by setup.
+        let reload = wwdg.cr.read().t().bits() & WWDG_MAX_RELOAD as u8;
+        WindowWatchdog { wwdg, reload }
+    }
+
+    /// Read the current configuration out of the hardware registers.
+    ///
+    /// This reads `CR.T`, `CFR.W`, `CFR.WDGTB` and `CR.WDGA` so the live
+    /// configuration can be obtained without re-running [`setup`].
+    ///
+    /// Note that `reload` is read from the live down-counter `CR.T` (the WWDG has
+    /// no separate reload register) and so only reflects the configured reload in
+    /// the instant immediately after a refresh; see [`from_registers`] for
+    /// details.
+    ///
+    /// [`from_registers`]: WindowWatchdog::from_registers
+    ///
+    /// [`setup`]: WindowWatchdog::start
+    pub fn config(&self) -> Config {
+        let cr = self.wwdg.cr.read();
+        let cfr = self.wwdg.cfr.read();
+
+        Config {
+            reload : cr.t().bits() & WWDG_MAX_RELOAD as u8,
+            window : cfr.w().bits(),
+            prescaler : cfr.wdgtb().bits(),
+            enabled : cr.wdga().bit_is_set(),
+        }
+    }
+
     /// Set the window for this watchdog to open after the given microseconds. 
     /// If the given target microseconds results in less than 1 clock cycle 
     /// a panic will be created by a debug_assert.
@@ -65,7 +197,32 @@ impl WindowWatchdog {
 
         debug_assert!(cycles > 0);
 
-        self.wwdg.cfr.modify(|_, w| w.w().bits(0x40 & cycles as u8));
+        // The down-counter T[6:0] runs from 0x7F down to 0x3F and a reset occurs
+        // the instant it drops below 0x40. A refresh while T > W also resets, so
+        // the window value W is offset by the same 0x40 base as the counter.
+        let window = (0x40 + cycles).min(0x7F) as u8;
+
+        debug_assert!(window >= 0x40 && window <= 0x40 + self.reload);
+
+        self.wwdg.cfr.modify(|_, w| w.w().bits(window));
+    }
+
+    /// Returns `true` if the early wakeup interrupt flag (`SR.EWIF`) is set.
+    ///
+    /// The flag is raised when the down-counter reaches 0x40, one cycle before
+    /// the reset fires, giving the EWI handler a chance to act.
+    #[inline]
+    pub fn is_early_wakeup(&self) -> bool {
+        self.wwdg.sr.read().ewif().bit_is_set()
+    }
+
+    /// Clear the early wakeup interrupt flag (`SR.EWIF`).
+    ///
+    /// This should be called from the WWDG ISR after servicing the early wakeup
+    /// interrupt, otherwise the interrupt will keep firing.
+    #[inline]
+    pub fn clear_early_wakeup(&self) {
+        self.wwdg.sr.write(|w| w.ewif().clear_bit());
     }
 
     /// Configure the watchdog to stop while the cpu is halted during debugging.
@@ -131,6 +288,30 @@ impl WindowWatchdog {
         &mut self.wwdg
     }
 
+    /// Program the reload, optional window and debug-halt freeze bit and
+    /// enable the watchdog in one atomic call from a [`WatchdogConfig`].
+    ///
+    /// The reload is computed as by `start`, from the prescaler currently held
+    /// in `CFR.WDGTB`; the prescaler itself is not written.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to apply.
+    /// * `rcc` - RCC Peripheral needed to determine the clock period.
+    /// * `dbg` - Instance of DBGMCU from PAC needed to configure the debug freeze.
+    #[cfg(not(feature = "stm32wl5x_cm0p"))]
+    pub fn start_from_config(&mut self, config : WatchdogConfig, rcc : &RCC, dbg : &pac::DBGMCU) {
+        self.stop_on_debug(dbg, !config.run_during_debug_halt);
+
+        self.setup(config.timeout_us, rcc);
+
+        if let Some(window_us) = config.window_us {
+            self.set_window_us(window_us, rcc);
+        }
+
+        self.wwdg.cr.modify(|_, w| w.wdga().enabled());
+    }
+
     #[inline]
     fn clock_period_us(&self, rcc : &RCC) -> u32 {
         let pclk = pclk1_hz(rcc);
@@ -172,7 +353,7 @@ impl WindowWatchdog {
 impl Watchdog for WindowWatchdog {
     #[inline]
     fn feed(&mut self) {
-        self.wwdg.cr.write(|w| w.t().bits(0x40 & self.reload))
+        self.wwdg.cr.write(|w| w.t().bits(0x40 | self.reload))
     }
 }
 
@@ -187,4 +368,154 @@ impl WatchdogEnable for WindowWatchdog {
 
         self.wwdg.cr.modify(|_, w|w.wdga().enabled());
     }
-}
\ No newline at end of file
+}
+
+/// Driver for the independent watchdog (IWDG) peripheral.
+///
+/// Unlike the [`WindowWatchdog`] the IWDG is clocked from the ~32 kHz LSI and
+/// keeps counting even if the main clock fails, making it suited to catching a
+/// total lockup of the device.
+pub struct IndependentWatchdog {
+    iwdg : IWDG,
+}
+
+impl IndependentWatchdog {
+    /// Create a new IndependentWatchdog.
+    ///
+    /// The IWDG is clocked by the LSI; ensure the LSI is running before
+    /// starting the watchdog.
+    ///
+    /// # Arguments
+    ///
+    /// * `iwdg` - IWDG Peripheral needed for the driver.
+    #[inline]
+    pub fn new(iwdg : IWDG) -> Self {
+        IndependentWatchdog { iwdg }
+    }
+
+    #[inline]
+    /// Steal the IndependentWatchdog from whatever is using it.
+    ///
+    /// This does NOT initialize the IndependentWatchdog (unlike [`new`]).
+    ///
+    /// # Safety
+    ///
+    /// 1. Ensure that the code stealing the IWDG peripheral has exclusive access.
+    ///    Singleton checks are bypassed with this method.
+    /// 2. You are responsible for ensuring the LSI is running before use.
+    ///
+    /// [`new`]: IndependentWatchdog::new
+    pub unsafe fn steal() -> Self {
+        let dp = pac::Peripherals::steal();
+        IndependentWatchdog { iwdg: dp.IWDG }
+    }
+
+    /// Configure the watchdog to stop while the cpu is halted during debugging.
+    ///
+    /// # Arguments
+    ///
+    /// * `dbg` - Instance of DBGMCU from PAC needed to configure.
+    /// * `stop` - Boolean to configure if the watchdog should be stopped while the cpu is halted.
+    #[cfg(not(feature = "stm32wl5x_cm0p"))]
+    #[inline]
+    pub fn stop_on_debug(&self, dbg : &pac::DBGMCU, stop : bool) {
+        dbg.apb1fzr1.modify(|_, w| w.dbg_iwdg_stop().bit(stop));
+    }
+
+    /// Get the currently configured interval of the watchdog in milliseconds.
+    ///
+    /// The interval is recomputed from the live PR/RLR registers so it stays
+    /// correct even after a [`steal`].
+    ///
+    /// [`steal`]: IndependentWatchdog::steal
+    pub fn interval_ms(&self) -> u32 {
+        let pr = self.iwdg.pr.read().pr().bits();
+        let rl = self.iwdg.rlr.read().rl().bits() as u32;
+
+        rl * Self::divisor(pr) * 1000 / LSI_FREQ
+    }
+
+    /// The clock divisor selected by the prescaler value `pr` (4·2^pr).
+    #[inline]
+    fn divisor(pr : u8) -> u32 {
+        4u32 << pr
+    }
+
+    /// The maximum period in milliseconds achievable with prescaler `pr` at the
+    /// maximum reload value.
+    #[inline]
+    fn max_period_ms(pr : u8) -> u32 {
+        IWDG_MAX_RELOAD * Self::divisor(pr) * 1000 / LSI_FREQ
+    }
+
+    /// Program the prescaler/reload and debug-halt freeze behaviour and
+    /// start the watchdog in one atomic call from a [`WatchdogConfig`].
+    ///
+    /// The `window_us` field is ignored as the IWDG has no window. The timeout
+    /// is taken from `timeout_us` and rounded to the nearest millisecond.
+    ///
+    /// Only the debug-halt behaviour is programmed here; the IWDG's behaviour in
+    /// the STM32WL's stop/standby low-power modes is fixed by the FLASH option
+    /// bytes and cannot be set at runtime (see [`WatchdogConfig`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration to apply.
+    /// * `dbg` - Instance of DBGMCU from PAC needed to configure the debug freeze.
+    #[cfg(not(feature = "stm32wl5x_cm0p"))]
+    pub fn start_from_config(&mut self, config : WatchdogConfig, dbg : &pac::DBGMCU) {
+        self.stop_on_debug(dbg, !config.run_during_debug_halt);
+
+        self.setup((config.timeout_us + 500) / 1000);
+
+        self.iwdg.kr.write(|w| w.key().bits(IWDG_KEY_START));
+    }
+
+    fn setup(&mut self, timeout_ms : u32) {
+        // Pick the smallest prescaler whose maximum period still covers the
+        // requested timeout.
+        let mut pr = 0;
+        while pr < IWDG_MAX_PR && Self::max_period_ms(pr) < timeout_ms {
+            pr += 1;
+        }
+
+        // Clamp to the selected prescaler's ceiling before the multiply: beyond
+        // it the reload already saturates to the maximum and `timeout_ms *
+        // IWDG_MAX_RELOAD` would otherwise overflow `u32`.
+        let max_period = Self::max_period_ms(pr);
+        let rl = (timeout_ms.min(max_period) * IWDG_MAX_RELOAD / max_period).min(IWDG_MAX_RELOAD);
+
+        // Unlock the registers, program the prescaler/reload and wait for the
+        // updates to propagate to the watchdog clock domain before starting.
+        self.iwdg.kr.write(|w| w.key().bits(IWDG_KEY_UNLOCK));
+        self.iwdg.pr.write(|w| w.pr().bits(pr));
+        self.iwdg.rlr.write(|w| w.rl().bits(rl as u16));
+
+        while self.iwdg.sr.read().pvu().bit_is_set() {}
+        while self.iwdg.sr.read().rvu().bit_is_set() {}
+    }
+}
+
+impl Watchdog for IndependentWatchdog {
+    #[inline]
+    fn feed(&mut self) {
+        self.iwdg.kr.write(|w| w.key().bits(IWDG_KEY_FEED))
+    }
+}
+
+impl WatchdogEnable for IndependentWatchdog {
+    type Time = u32;
+
+    /// Start the watchdog with the given timeout in **milliseconds**.
+    ///
+    /// Note the unit: [`WindowWatchdog`]'s `start` takes **microseconds** for the
+    /// same `Time = u32`. Code generic over [`WatchdogEnable`] must account for
+    /// this 1000× difference when swapping between the two drivers.
+    fn start<T>(&mut self, period: T)
+    where
+        T: Into<Self::Time> {
+        self.setup(period.into());
+
+        self.iwdg.kr.write(|w| w.key().bits(IWDG_KEY_START));
+    }
+}
@@ -0,0 +1,70 @@
+#![no_std]
+#![no_main]
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+
+#[defmt_test::tests]
+mod tests {
+    use defmt::*;
+    use nucleo_wl55jc_bsp::hal::{
+        watchdog::IndependentWatchdog,
+        embedded_hal::watchdog::WatchdogEnable,
+        rcc,
+        pac::{
+            self,
+            interrupt
+        }
+    };
+
+    const MSI_FREQ : u32 = 8_000_000;
+
+    struct TestArgs {
+        iwdg: pac::IWDG,
+    }
+
+    #[init]
+    fn init() -> TestArgs {
+        let mut dp: pac::Peripherals = unwrap!(pac::Peripherals::take());
+
+        cortex_m::interrupt::free(|cs| unsafe {
+            rcc::set_sysclk_hsi(
+                &mut dp.FLASH,
+                &mut dp.PWR,
+                &mut dp.RCC,
+                cs,
+            )
+        });
+
+        assert_eq!(rcc::sysclk_hz(&dp.RCC), MSI_FREQ);
+
+        TestArgs {
+            iwdg: dp.IWDG,
+        }
+    }
+
+    // The shortest prescaler (/4) caps the period at ~511 ms, so a 500 ms
+    // timeout must stay on that prescaler and read back close to the request.
+    #[test]
+    fn short_timeout_uses_smallest_prescaler(ta: &mut TestArgs) {
+        let mut wdg = IndependentWatchdog::new(ta.iwdg);
+
+        wdg.start(500u32);
+
+        let interval = wdg.interval_ms();
+        assert!(interval >= 450 && interval <= 511, "interval {} out of range", interval);
+    }
+
+    // A timeout beyond the /4 prescaler's ~511 ms ceiling must bump the
+    // prescaler up and still cover the requested period.
+    #[test]
+    fn long_timeout_bumps_prescaler(ta: &mut TestArgs) {
+        let mut wdg = IndependentWatchdog::new(ta.iwdg);
+
+        wdg.start(4000u32);
+
+        let interval = wdg.interval_ms();
+        assert!(interval >= 3600 && interval <= 4000, "interval {} out of range", interval);
+    }
+}